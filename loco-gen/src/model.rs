@@ -1,14 +1,19 @@
-use std::{collections::HashMap, env::current_dir};
+use std::{
+    collections::{HashMap, HashSet},
+    env::current_dir,
+};
 
 use chrono::Utc;
 use duct::cmd;
 use rrgen::RRgen;
+use serde::Serialize;
 use serde_json::json;
 
 use super::{Error, Result};
 use crate::get_mappings;
 
 const MODEL_T: &str = include_str!("templates/model.t");
+const MODEL_DOWN_T: &str = include_str!("templates/model_down.t");
 const MODEL_TEST_T: &str = include_str!("templates/model_test.t");
 
 use super::{collect_messages, AppInfo};
@@ -18,11 +23,149 @@ use super::{collect_messages, AppInfo};
 /// generated by the Loco app and should be given
 pub const IGNORE_FIELDS: &[&str] = &["created_at", "updated_at", "create_at", "update_at"];
 
+/// the column modifiers recognized after the base type, e.g.
+/// `email:string:unique` or `status:string:default=active`
+const COLUMN_MODIFIERS: &[&str] = &["null", "unique", "index", "default=<value>"];
+
+/// a single column in the generated migration, carrying the builder flags
+/// `model.t` needs beyond the bare sea-orm column type.
+#[derive(Debug, Clone, Serialize)]
+struct Column {
+    name: String,
+    col_type: String,
+    null: bool,
+    unique: bool,
+    index: bool,
+    default: Option<String>,
+}
+
+impl Column {
+    fn new(name: &str, col_type: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            col_type: col_type.to_string(),
+            null: false,
+            unique: false,
+            index: false,
+            default: None,
+        }
+    }
+}
+
+/// splits a field type token like `string:unique:default=active` into its
+/// base type and the set of recognized modifiers.
+fn parse_column_modifiers(fname: &str, ftype: &str) -> Result<(String, Column)> {
+    let mut parts = ftype.split(':');
+    let base_type = parts.next().unwrap_or_default().to_string();
+    let mut column = Column::new(fname, "");
+    for modifier in parts {
+        if modifier == "null" {
+            column.null = true;
+        } else if modifier == "unique" {
+            column.unique = true;
+        } else if modifier == "index" {
+            column.index = true;
+        } else if let Some(value) = modifier.strip_prefix("default=") {
+            column.default = Some(value.to_string());
+        } else {
+            return Err(Error::Message(format!(
+                "modifier: {modifier} not found. try any of: {COLUMN_MODIFIERS:?}"
+            )));
+        }
+    }
+    Ok((base_type, column))
+}
+
+/// the modifiers recognized after a `references:<table>` spec, e.g.
+/// `references:users:on_delete=cascade` or `references:categories:fk=parent_id`
+const REFERENCE_MODIFIERS: &[&str] = &["on_delete=<action>", "on_update=<action>", "fk=<column>"];
+
+/// the foreign key actions sea-orm-migration's `ForeignKeyAction` supports.
+const FK_ACTIONS: &[&str] = &[
+    "cascade",
+    "restrict",
+    "set_null",
+    "set_default",
+    "no_action",
+];
+
+/// a `references` field, carrying enough to emit a real
+/// `ForeignKey::create()` statement instead of a bare integer column.
+#[derive(Debug, Clone, Serialize)]
+struct Reference {
+    table: String,
+    column: String,
+    on_delete: Option<String>,
+    on_update: Option<String>,
+    /// the reference points back at the model currently being generated
+    self_referential: bool,
+}
+
+fn parse_fk_action(modifier: &str, action: &str) -> Result<String> {
+    if FK_ACTIONS.contains(&action) {
+        Ok(action.to_string())
+    } else {
+        Err(Error::Message(format!(
+            "{modifier}: {action} not found. try any of: {FK_ACTIONS:?}"
+        )))
+    }
+}
+
+/// parses a `references:<table>[:modifier]*` spec (with `spec` being
+/// everything after the `references:` prefix, or just the field name for a
+/// bare `references` type) into a structured foreign key description.
+fn parse_reference(fname: &str, spec: &str, model_name: &str) -> Result<Reference> {
+    let mut parts = spec.split(':');
+    let table = parts.next().unwrap_or(fname).to_string();
+    let mut column = format!("{fname}_id");
+    let mut on_delete = None;
+    let mut on_update = None;
+    for modifier in parts {
+        if let Some(action) = modifier.strip_prefix("on_delete=") {
+            on_delete = Some(parse_fk_action("on_delete", action)?);
+        } else if let Some(action) = modifier.strip_prefix("on_update=") {
+            on_update = Some(parse_fk_action("on_update", action)?);
+        } else if let Some(fk_column) = modifier.strip_prefix("fk=") {
+            column = fk_column.to_string();
+        } else {
+            return Err(Error::Message(format!(
+                "modifier: {modifier} not found. try any of: {REFERENCE_MODIFIERS:?}"
+            )));
+        }
+    }
+    let self_referential = table == model_name;
+    Ok(Reference {
+        table,
+        column,
+        on_delete,
+        on_update,
+        self_referential,
+    })
+}
+
+/// how far `generate` is allowed to go beyond rendering templates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GenerateMode {
+    /// render the migration/model files and apply them with `cargo
+    /// loco-tool db migrate` + `db entities`.
+    Apply,
+    /// render the migration/model files but don't apply them; `db migrate`
+    /// and `db entities` are left for the caller to run later.
+    MigrationOnly,
+    /// preview what `MigrationOnly` would do without touching disk at all:
+    /// no migration/model/test file is rendered or written, and no `cargo
+    /// loco-tool` subcommand runs. returns a summary of the columns,
+    /// references and indexes that would have been generated, for callers
+    /// that want to show the user a plan before committing to it.
+    DryRun,
+}
+
 pub fn generate(
     rrgen: &RRgen,
     name: &str,
     is_link: bool,
-    migration_only: bool,
+    mode: GenerateMode,
+    reversible: bool,
     fields: &[(String, String)],
     appinfo: &AppInfo,
 ) -> Result<String> {
@@ -40,32 +183,74 @@ pub fn generate(
             continue;
         }
         if ftype == "references" {
-            let fkey = format!("{fname}_id");
-            columns.push((fkey.clone(), "integer"));
             // user, user_id
-            references.push((fname.to_string(), fkey));
+            let reference = parse_reference(fname, fname, name)?;
+            columns.push(Column::new(&reference.column, "integer"));
+            references.push(reference);
         } else if ftype.starts_with("references:") {
-            let fkey = format!("{fname}_id");
-            columns.push((fkey.clone(), "integer"));
-            references.push((ftype["references:".len()..].to_string(), fkey));
+            let reference = parse_reference(fname, &ftype["references:".len()..], name)?;
+            columns.push(Column::new(&reference.column, "integer"));
+            references.push(reference);
         } else {
+            let (base_type, mut column) = parse_column_modifiers(fname, ftype)?;
             let mappings = get_mappings();
-            let schema_type = mappings.schema_field(ftype.as_str()).ok_or_else(|| {
+            let schema_type = mappings.schema_field(base_type.as_str()).ok_or_else(|| {
                 Error::Message(format!(
                     "type: {} not found. try any of: {:?}",
-                    ftype,
+                    base_type,
                     mappings.schema_fields()
                 ))
             })?;
-            columns.push((fname.to_string(), schema_type.as_str()));
+            column.col_type = schema_type.to_string();
+            columns.push(column);
         }
     }
 
-    let vars = json!({"name": name, "ts": ts, "pkg_name": pkg_name, "is_link": is_link, "columns": columns, "references": references});
+    // `generate` always creates a brand-new table, so the `down` migration
+    // just has to undo that: drop any foreign keys first, then the table
+    // itself (which takes its columns down with it).
+    let drop_columns: Vec<String> = columns.iter().map(|column| column.name.clone()).collect();
+    let drop_foreign_keys: Vec<String> = references.iter().map(|r| r.column.clone()).collect();
+    let indexes: Vec<String> = columns
+        .iter()
+        .filter(|column| column.index)
+        .map(|column| column.name.clone())
+        .collect();
+
+    if mode == GenerateMode::DryRun {
+        let column_names: Vec<&str> = columns.iter().map(|c| c.name.as_str()).collect();
+        let reference_tables: Vec<&str> = references.iter().map(|r| r.table.as_str()).collect();
+        let preview = format!(
+            "dry run: would generate a{} migration, model and test for `{name}` \
+             with columns {column_names:?}, references {reference_tables:?} and \
+             indexes {indexes:?} -- no files were written and no `cargo loco-tool` \
+             subcommand was run",
+            if reversible { " reversible" } else { "n" },
+        );
+        tracing::info!(migration = %preview, "dry run");
+        return Ok(preview);
+    }
+
+    let vars = json!({
+        "name": name,
+        "ts": ts,
+        "pkg_name": pkg_name,
+        "is_link": is_link,
+        "columns": columns,
+        "references": references,
+        "indexes": indexes,
+        "reversible": reversible,
+        "drop_table": true,
+        "drop_columns": drop_columns,
+        "drop_foreign_keys": drop_foreign_keys,
+    });
     let res1 = rrgen.generate(MODEL_T, &vars)?;
+    let res_down = reversible
+        .then(|| rrgen.generate(MODEL_DOWN_T, &vars))
+        .transpose()?;
     let res2 = rrgen.generate(MODEL_TEST_T, &vars)?;
 
-    if !migration_only {
+    if mode == GenerateMode::Apply {
         let cwd = current_dir()?;
         let env_map: HashMap<_, _> = std::env::vars().collect();
 
@@ -91,10 +276,240 @@ pub fn generate(
             })?;
     }
 
-    let messages = collect_messages(vec![res1, res2]);
+    let mut results = vec![res1, res2];
+    if let Some(down) = res_down {
+        results.push(down);
+    }
+    let messages = collect_messages(results);
+
     Ok(messages)
 }
 
+/// reverse-engineers a model's field list from a table that already exists
+/// in the database, the way `diesel print_schema`/`infer_schema` derive
+/// structs from a live schema, then feeds it through the same pipeline as
+/// [`generate`]. Lets a user adopt Loco on top of a legacy database instead
+/// of hand-retyping every column.
+pub async fn generate_from_schema(
+    rrgen: &RRgen,
+    db_url: &str,
+    table: &str,
+    is_link: bool,
+    mode: GenerateMode,
+    reversible: bool,
+    appinfo: &AppInfo,
+) -> Result<String> {
+    let fields = introspect_table(db_url, table).await?;
+
+    generate(rrgen, table, is_link, mode, reversible, &fields, appinfo)
+}
+
+/// sqlx's `Any` driver doesn't translate bind-placeholder syntax between
+/// backends, so callers have to pick the one the underlying driver expects.
+fn bind_placeholder(db_url: &str) -> &'static str {
+    if db_url.starts_with("postgres:") || db_url.starts_with("postgresql:") {
+        "$1"
+    } else {
+        // both the MySQL and SQLite drivers use positional `?` placeholders
+        "?"
+    }
+}
+
+/// the `PRAGMA table_info(...)`/`PRAGMA foreign_key_list(...)` calls can't
+/// bind `table` as a parameter (SQLite only accepts identifiers there as
+/// literal SQL), so it's interpolated directly -- guard against that by
+/// rejecting anything that isn't a plain SQL identifier before it ever
+/// reaches a query.
+fn validate_table_identifier(table: &str) -> Result<()> {
+    let is_identifier = table
+        .chars()
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && table.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if is_identifier {
+        Ok(())
+    } else {
+        Err(Error::Message(format!(
+            "table: `{table}` is not a valid identifier, expected to match ^[A-Za-z_][A-Za-z0-9_]*$"
+        )))
+    }
+}
+
+/// connects to `db_url` and reads back a `(field_name, field_type)` list for
+/// `table`, in the same shape `generate`'s `fields` argument expects.
+async fn introspect_table(db_url: &str, table: &str) -> Result<Vec<(String, String)>> {
+    use sqlx::{any::AnyConnection, Connection, Row};
+
+    validate_table_identifier(table)?;
+
+    let mut conn = AnyConnection::connect(db_url)
+        .await
+        .map_err(|err| Error::Message(format!("failed to connect to `{db_url}`: {err}")))?;
+
+    let foreign_keys = introspect_foreign_keys(&mut conn, db_url, table).await?;
+    let primary_key_columns = introspect_primary_key_columns(&mut conn, db_url, table).await?;
+    let is_sqlite = db_url.starts_with("sqlite:");
+
+    let rows = if is_sqlite {
+        sqlx::query(&format!("PRAGMA table_info({table})"))
+            .fetch_all(&mut conn)
+            .await
+    } else {
+        sqlx::query(&format!(
+            "select column_name, data_type, is_nullable from information_schema.columns \
+             where table_name = {} order by ordinal_position",
+            bind_placeholder(db_url)
+        ))
+        .bind(table)
+        .fetch_all(&mut conn)
+        .await
+    }
+    .map_err(|err| Error::Message(format!("failed to read columns for `{table}`: {err}")))?;
+
+    let mappings = get_mappings();
+    let mut fields = Vec::new();
+    for row in rows {
+        let (column_name, data_type, nullable): (String, String, bool) = if is_sqlite {
+            (
+                row.try_get("name")?,
+                row.try_get("type")?,
+                row.try_get::<i64, _>("notnull")? == 0,
+            )
+        } else {
+            let is_nullable: String = row.try_get("is_nullable")?;
+            (
+                row.try_get("column_name")?,
+                row.try_get("data_type")?,
+                is_nullable == "YES",
+            )
+        };
+
+        // `id` is filtered the same way as `created_at`/`updated_at`: `model.t`
+        // always injects its own auto-managed primary key column, so carrying
+        // one over here would generate a migration with a duplicate column.
+        if IGNORE_FIELDS.contains(&column_name.as_str())
+            || primary_key_columns.contains(&column_name)
+        {
+            continue;
+        }
+
+        // re-express `_id` foreign key columns back as `references:` entries
+        // so relations round-trip instead of showing up as plain integers.
+        if let Some(target_table) = foreign_keys.get(&column_name) {
+            let fname = column_name
+                .strip_suffix("_id")
+                .unwrap_or(&column_name)
+                .to_string();
+            let spec = if target_table == &fname {
+                "references".to_string()
+            } else {
+                format!("references:{target_table}")
+            };
+            fields.push((fname, spec));
+            continue;
+        }
+
+        let schema_field = mappings.rust_field(&data_type).ok_or_else(|| {
+            Error::Message(format!(
+                "database type: {data_type} on column `{column_name}` has no known schema field mapping"
+            ))
+        })?;
+        let field_type = if nullable {
+            format!("{schema_field}:null")
+        } else {
+            schema_field.to_string()
+        };
+        fields.push((column_name, field_type));
+    }
+
+    Ok(fields)
+}
+
+/// maps `{local column name -> referenced table}` for every foreign key on
+/// `table`.
+async fn introspect_foreign_keys(
+    conn: &mut sqlx::AnyConnection,
+    db_url: &str,
+    table: &str,
+) -> Result<HashMap<String, String>> {
+    use sqlx::Row;
+
+    let mut foreign_keys = HashMap::new();
+    if db_url.starts_with("sqlite:") {
+        let rows = sqlx::query(&format!("PRAGMA foreign_key_list({table})"))
+            .fetch_all(conn)
+            .await
+            .map_err(|err| Error::Message(format!("failed to read foreign keys: {err}")))?;
+        for row in rows {
+            let local_column: String = row.try_get("from")?;
+            let referenced_table: String = row.try_get("table")?;
+            foreign_keys.insert(local_column, referenced_table);
+        }
+    } else {
+        let rows = sqlx::query(&format!(
+            "select kcu.column_name, ccu.table_name as referenced_table \
+             from information_schema.key_column_usage kcu \
+             join information_schema.table_constraints tc \
+               on kcu.constraint_name = tc.constraint_name \
+             join information_schema.constraint_column_usage ccu \
+               on kcu.constraint_name = ccu.constraint_name \
+             where kcu.table_name = {} and tc.constraint_type = 'FOREIGN KEY'",
+            bind_placeholder(db_url)
+        ))
+        .bind(table)
+        .fetch_all(conn)
+        .await
+        .map_err(|err| Error::Message(format!("failed to read foreign keys: {err}")))?;
+        for row in rows {
+            let local_column: String = row.try_get("column_name")?;
+            let referenced_table: String = row.try_get("referenced_table")?;
+            foreign_keys.insert(local_column, referenced_table);
+        }
+    }
+    Ok(foreign_keys)
+}
+
+/// the set of column names making up `table`'s primary key, so they can be
+/// filtered out the same way the auto-managed `created_at`/`updated_at`
+/// columns are -- `model.t` always injects its own `id` primary key.
+async fn introspect_primary_key_columns(
+    conn: &mut sqlx::AnyConnection,
+    db_url: &str,
+    table: &str,
+) -> Result<HashSet<String>> {
+    use sqlx::Row;
+
+    let mut primary_key_columns = HashSet::new();
+    if db_url.starts_with("sqlite:") {
+        let rows = sqlx::query(&format!("PRAGMA table_info({table})"))
+            .fetch_all(conn)
+            .await
+            .map_err(|err| Error::Message(format!("failed to read primary key: {err}")))?;
+        for row in rows {
+            if row.try_get::<i64, _>("pk")? != 0 {
+                primary_key_columns.insert(row.try_get("name")?);
+            }
+        }
+    } else {
+        let rows = sqlx::query(&format!(
+            "select kcu.column_name \
+             from information_schema.key_column_usage kcu \
+             join information_schema.table_constraints tc \
+               on kcu.constraint_name = tc.constraint_name \
+             where tc.table_name = {} and tc.constraint_type = 'PRIMARY KEY'",
+            bind_placeholder(db_url)
+        ))
+        .bind(table)
+        .fetch_all(conn)
+        .await
+        .map_err(|err| Error::Message(format!("failed to read primary key: {err}")))?;
+        for row in rows {
+            primary_key_columns.insert(row.try_get("column_name")?);
+        }
+    }
+    Ok(primary_key_columns)
+}
+
 #[cfg(test)]
 mod tests {
     use std::{env, process::Command};
@@ -104,6 +519,8 @@ mod tests {
         AppInfo,
     };
 
+    use super::GenerateMode;
+
     fn with_new_app<F>(app_name: &str, f: F)
     where
         F: FnOnce(),
@@ -143,7 +560,8 @@ mod tests {
                 &rrgen,
                 "movies",
                 false,
-                true,
+                GenerateMode::MigrationOnly,
+                false,
                 &[("title".to_string(), "string".to_string())],
                 &AppInfo {
                     app_name: "saas".to_string(),
@@ -162,4 +580,223 @@ mod tests {
             assert_cargo_check();
         });
     }
+
+    #[test]
+    fn test_can_generate_reversible_migration() {
+        let rrgen = rrgen::RRgen::default();
+        with_new_app("saas", || {
+            super::generate(
+                &rrgen,
+                "movies",
+                false,
+                GenerateMode::MigrationOnly,
+                true,
+                &[("title".to_string(), "string".to_string())],
+                &AppInfo {
+                    app_name: "saas".to_string(),
+                },
+            )
+            .expect("generate");
+            let migration = assert_single_file_match("migration/src", ".*_movies.rs$");
+            assert_file(migration.to_str().unwrap(), |content| {
+                content.assert_syntax();
+                content.assert_regex_match(r"async fn down\(&self, manager: &SchemaManager\) -> Result<\(\), DbErr> \{\s*\S");
+            });
+            assert_cargo_check();
+        });
+    }
+
+    #[test]
+    fn test_can_generate_model_with_column_modifiers() {
+        let rrgen = rrgen::RRgen::default();
+        with_new_app("saas", || {
+            super::generate(
+                &rrgen,
+                "users",
+                false,
+                GenerateMode::MigrationOnly,
+                false,
+                &[
+                    ("email".to_string(), "string:unique".to_string()),
+                    ("bio".to_string(), "text:null".to_string()),
+                    ("status".to_string(), "string:default=active".to_string()),
+                    ("slug".to_string(), "string:index".to_string()),
+                ],
+                &AppInfo {
+                    app_name: "saas".to_string(),
+                },
+            )
+            .expect("generate");
+            let migration = assert_single_file_match("migration/src", ".*_users.rs$");
+            assert_file(migration.to_str().unwrap(), |content| {
+                content.assert_syntax();
+                content.assert_regex_match(r"\.unique_key\(\)");
+                content.assert_regex_match(r"\.null\(\)");
+                content.assert_regex_match("create_index");
+            });
+            assert_cargo_check();
+        });
+    }
+
+    #[test]
+    fn test_unknown_column_modifier_is_rejected() {
+        let rrgen = rrgen::RRgen::default();
+        let res = super::generate(
+            &rrgen,
+            "users",
+            false,
+            GenerateMode::MigrationOnly,
+            false,
+            &[("email".to_string(), "string:unknown".to_string())],
+            &AppInfo {
+                app_name: "saas".to_string(),
+            },
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_can_generate_model_with_fk_actions() {
+        let rrgen = rrgen::RRgen::default();
+        with_new_app("saas", || {
+            super::generate(
+                &rrgen,
+                "posts",
+                false,
+                GenerateMode::MigrationOnly,
+                false,
+                &[
+                    (
+                        "author".to_string(),
+                        "references:users:on_delete=cascade".to_string(),
+                    ),
+                    (
+                        "editor".to_string(),
+                        "references:users:fk=editor_user_id".to_string(),
+                    ),
+                ],
+                &AppInfo {
+                    app_name: "saas".to_string(),
+                },
+            )
+            .expect("generate");
+            let migration = assert_single_file_match("migration/src", ".*_posts.rs$");
+            assert_file(migration.to_str().unwrap(), |content| {
+                content.assert_syntax();
+                content.assert_regex_match(r"\.on_delete\(ForeignKeyAction::Cascade\)");
+                content.assert_regex_match("editor_user_id");
+            });
+            assert_cargo_check();
+        });
+    }
+
+    #[test]
+    fn test_unknown_fk_action_is_rejected() {
+        let rrgen = rrgen::RRgen::default();
+        let res = super::generate(
+            &rrgen,
+            "posts",
+            false,
+            GenerateMode::MigrationOnly,
+            false,
+            &[(
+                "author".to_string(),
+                "references:users:on_delete=unknown".to_string(),
+            )],
+            &AppInfo {
+                app_name: "saas".to_string(),
+            },
+        );
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_introspect_table_recovers_fields() {
+        use sqlx::{any::AnyConnection, Connection, Executor};
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db_url = format!(
+            "sqlite://{}?mode=rwc",
+            dir.path().join("schema.db").display()
+        );
+
+        let mut conn = AnyConnection::connect(&db_url)
+            .await
+            .expect("connect to sqlite db");
+        conn.execute(
+            "create table movies (\
+                id integer primary key, \
+                title text not null, \
+                rating text, \
+                created_at text not null, \
+                updated_at text not null\
+            )",
+        )
+        .await
+        .expect("create table");
+        conn.close().await.expect("close connection");
+
+        let fields = super::introspect_table(&db_url, "movies")
+            .await
+            .expect("introspect_table");
+
+        assert!(fields.contains(&("title".to_string(), "string".to_string())));
+        assert!(fields.contains(&("rating".to_string(), "string:null".to_string())));
+        assert!(!fields.iter().any(|(name, _)| name == "id"));
+        assert!(!fields.iter().any(|(name, _)| name == "created_at"));
+        assert!(!fields.iter().any(|(name, _)| name == "updated_at"));
+    }
+
+    #[test]
+    fn test_bind_placeholder_by_backend() {
+        assert_eq!(super::bind_placeholder("postgres://localhost/app"), "$1");
+        assert_eq!(super::bind_placeholder("postgresql://localhost/app"), "$1");
+        assert_eq!(super::bind_placeholder("mysql://localhost/app"), "?");
+        assert_eq!(super::bind_placeholder("sqlite://db.sqlite"), "?");
+    }
+
+    #[test]
+    fn test_validate_table_identifier_rejects_sql_injection() {
+        assert!(super::validate_table_identifier("movies").is_ok());
+        assert!(super::validate_table_identifier("_movies_2").is_ok());
+        assert!(super::validate_table_identifier("movies; drop table users;--").is_err());
+        assert!(super::validate_table_identifier("movies)").is_err());
+        assert!(super::validate_table_identifier("2movies").is_err());
+        assert!(super::validate_table_identifier("").is_err());
+    }
+
+    #[test]
+    fn test_dry_run_writes_no_files_and_does_not_spawn_loco_tool() {
+        let rrgen = rrgen::RRgen::default();
+        with_new_app("saas", || {
+            // there is no `cargo loco-tool` on PATH in this sandboxed temp
+            // app, so if dry run ever shelled out, this would fail.
+            let messages = super::generate(
+                &rrgen,
+                "movies",
+                false,
+                GenerateMode::DryRun,
+                false,
+                &[("title".to_string(), "string".to_string())],
+                &AppInfo {
+                    app_name: "saas".to_string(),
+                },
+            )
+            .expect("generate");
+            assert!(messages.contains("movies"));
+
+            // unlike `MigrationOnly`, `DryRun` must not render anything to
+            // disk -- confirm no `*_movies.rs` migration/model file showed up.
+            let wrote_a_file = ["migration/src", "src/models"].iter().any(|dir| {
+                std::fs::read_dir(dir)
+                    .map(|entries| {
+                        entries
+                            .filter_map(|entry| entry.ok())
+                            .any(|entry| entry.file_name().to_string_lossy().contains("movies"))
+                    })
+                    .unwrap_or(false)
+            });
+            assert!(!wrote_a_file, "dry run should not have written any files");
+        });
+    }
 }